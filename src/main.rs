@@ -1,21 +1,449 @@
 use bracket_noise::prelude::FastNoise;
-use image::{ImageOutputFormat, Rgb, RgbImage};
+use image::{ImageBuffer, ImageOutputFormat, Luma, Rgb, RgbImage};
 use rand::{rngs::StdRng, thread_rng, RngCore, SeedableRng};
 use std::io::Cursor;
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlTableElement;
 
+/// The noise accumulation strategy used by [`Map::generate`].
+///
+/// `Fbm` is the classic signed fractal-Brownian-motion sum. `Turbulence`
+/// takes the absolute value of each octave before accumulating, which
+/// produces billowy, cloud-like terrain. `Ridged` inverts and squares the
+/// absolute value, carving sharp mountain ridges.
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum NoiseKind {
+    Fbm,
+    Turbulence,
+    Ridged,
+}
+
+impl NoiseKind {
+    fn apply(self, n: f32) -> f32 {
+        match self {
+            NoiseKind::Fbm => n,
+            NoiseKind::Turbulence => n.abs(),
+            NoiseKind::Ridged => {
+                let n = 1.0 - n.abs();
+                n * n
+            }
+        }
+    }
+}
+
 fn generate_layer(
     noise: &mut FastNoise,
     size: (usize, usize),
     scale: f32,
     offset: f32,
+    transform: impl Fn(f32) -> f32,
     output: &mut Vec<f32>,
 ) {
     for y in 0..size.1 {
         for x in 0..size.0 {
-            output[x + y * size.0] += noise.get_noise(x as f32, y as f32) * scale + offset;
+            output[x + y * size.0] += transform(noise.get_noise(x as f32, y as f32)) * scale + offset;
+        }
+    }
+}
+
+/// Like [`generate_layer`], but samples a 3D noise field on a unit sphere
+/// instead of the raw 2D grid coordinates, so the result tiles seamlessly
+/// around a planet with no east-west seam or pole distortion.
+fn generate_layer_spherical(
+    noise: &mut FastNoise,
+    size: (usize, usize),
+    scale: f32,
+    offset: f32,
+    transform: impl Fn(f32) -> f32,
+    output: &mut Vec<f32>,
+) {
+    // `noise`'s frequency is tuned for 2D grid coordinates, where adjacent
+    // cells are one unit apart; scale the unit sphere so adjacent cells along
+    // the equator are also one unit apart, so sampled feature size matches
+    // `generate_layer`'s.
+    let radius = size.0 as f32 / std::f32::consts::TAU;
+
+    for y in 0..size.1 {
+        let lat = y as f32 / size.1 as f32 * std::f32::consts::PI - std::f32::consts::FRAC_PI_2;
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        for x in 0..size.0 {
+            let lon = x as f32 / size.0 as f32 * std::f32::consts::TAU;
+            let (sin_lon, cos_lon) = lon.sin_cos();
+
+            let px = cos_lat * cos_lon * radius;
+            let py = cos_lat * sin_lon * radius;
+            let pz = sin_lat * radius;
+
+            output[x + y * size.0] += transform(noise.get_noise3d(px, py, pz)) * scale + offset;
+        }
+    }
+}
+
+/// Rescales `data` in place so its values span `[0, 1]`.
+///
+/// `Fbm` accumulation is already bounded to roughly that range, but
+/// `Turbulence` and `Ridged` shift it, so callers using those kinds need to
+/// renormalize afterwards.
+fn normalize(data: &mut [f32]) {
+    let (min, max) = data
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), &v| (min.min(v), max.max(v)));
+    let range = (max - min).max(f32::EPSILON);
+    for v in data {
+        *v = (*v - min) / range;
+    }
+}
+
+/// Tuning constants for the droplet erosion simulation in [`simulate_droplet`].
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct ErosionParams {
+    /// How strongly a droplet keeps its previous direction instead of
+    /// following the steepest descent.
+    inertia: f32,
+    /// Scales carrying capacity; higher values erode more aggressively.
+    capacity_factor: f32,
+    /// Floor on the slope used for capacity, so flat ground still erodes a little.
+    min_slope: f32,
+    /// Fraction of the capacity deficit eroded from the terrain each step.
+    erode_speed: f32,
+    /// Fraction of excess sediment deposited each step.
+    deposit_speed: f32,
+    /// Fraction of water lost to evaporation each step.
+    evaporation: f32,
+    /// Converts height loss/gain into droplet acceleration.
+    gravity: f32,
+    /// Radius, in cells, of the brush used when eroding terrain.
+    radius: usize,
+    /// Maximum number of steps a droplet is simulated for.
+    max_lifetime: usize,
+}
+
+#[wasm_bindgen]
+impl ErosionParams {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        ErosionParams::default()
+    }
+
+    pub fn set_inertia(&mut self, inertia: f32) {
+        self.inertia = inertia;
+    }
+
+    pub fn set_capacity_factor(&mut self, capacity_factor: f32) {
+        self.capacity_factor = capacity_factor;
+    }
+
+    pub fn set_min_slope(&mut self, min_slope: f32) {
+        self.min_slope = min_slope;
+    }
+
+    pub fn set_erode_speed(&mut self, erode_speed: f32) {
+        self.erode_speed = erode_speed;
+    }
+
+    pub fn set_deposit_speed(&mut self, deposit_speed: f32) {
+        self.deposit_speed = deposit_speed;
+    }
+
+    pub fn set_evaporation(&mut self, evaporation: f32) {
+        self.evaporation = evaporation;
+    }
+
+    pub fn set_gravity(&mut self, gravity: f32) {
+        self.gravity = gravity;
+    }
+
+    pub fn set_radius(&mut self, radius: usize) {
+        self.radius = radius;
+    }
+
+    pub fn set_max_lifetime(&mut self, max_lifetime: usize) {
+        self.max_lifetime = max_lifetime;
+    }
+}
+
+impl Default for ErosionParams {
+    fn default() -> Self {
+        ErosionParams {
+            inertia: 0.05,
+            capacity_factor: 4.0,
+            min_slope: 0.01,
+            erode_speed: 0.3,
+            deposit_speed: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+            radius: 2,
+            max_lifetime: 30,
+        }
+    }
+}
+
+/// Bilinearly samples the height and gradient of `data` at `pos`.
+fn height_and_gradient(data: &[f32], size: (usize, usize), pos: (f32, f32)) -> (f32, (f32, f32)) {
+    let x0 = pos.0.floor() as usize;
+    let y0 = pos.1.floor() as usize;
+    let fx = pos.0 - x0 as f32;
+    let fy = pos.1 - y0 as f32;
+
+    let at = |x: usize, y: usize| data[x.min(size.0 - 1) + y.min(size.1 - 1) * size.0];
+
+    let h00 = at(x0, y0);
+    let h10 = at(x0 + 1, y0);
+    let h01 = at(x0, y0 + 1);
+    let h11 = at(x0 + 1, y0 + 1);
+
+    let gradient = (
+        (h10 - h00) * (1.0 - fy) + (h11 - h01) * fy,
+        (h01 - h00) * (1.0 - fx) + (h11 - h10) * fx,
+    );
+    let height =
+        h00 * (1.0 - fx) * (1.0 - fy) + h10 * fx * (1.0 - fy) + h01 * (1.0 - fx) * fy + h11 * fx * fy;
+
+    (height, gradient)
+}
+
+/// Bilinearly deposits `amount` of sediment onto the cells surrounding `pos`.
+fn deposit(data: &mut [f32], size: (usize, usize), pos: (f32, f32), amount: f32) {
+    let x0 = pos.0.floor() as usize;
+    let y0 = pos.1.floor() as usize;
+    let fx = pos.0 - x0 as f32;
+    let fy = pos.1 - y0 as f32;
+
+    let mut add = |x: usize, y: usize, weight: f32| {
+        if x < size.0 && y < size.1 {
+            data[x + y * size.0] += amount * weight;
         }
+    };
+    add(x0, y0, (1.0 - fx) * (1.0 - fy));
+    add(x0 + 1, y0, fx * (1.0 - fy));
+    add(x0, y0 + 1, (1.0 - fx) * fy);
+    add(x0 + 1, y0 + 1, fx * fy);
+}
+
+/// Erodes `amount` of sediment from the terrain in a small brush around `pos`,
+/// weighted so nearer cells lose more than cells near the brush's edge.
+fn erode(data: &mut [f32], size: (usize, usize), pos: (f32, f32), amount: f32, radius: usize) {
+    let cx = pos.0.floor() as isize;
+    let cy = pos.1.floor() as isize;
+    let r = radius as isize;
+
+    let mut weights = Vec::new();
+    let mut total = 0.0;
+    for dy in -r..=r {
+        for dx in -r..=r {
+            let (x, y) = (cx + dx, cy + dy);
+            if x < 0 || y < 0 || x as usize >= size.0 || y as usize >= size.1 {
+                continue;
+            }
+            let dist = ((dx * dx + dy * dy) as f32).sqrt();
+            if dist > radius as f32 {
+                continue;
+            }
+            let weight = radius as f32 - dist;
+            total += weight;
+            weights.push((x as usize, y as usize, weight));
+        }
+    }
+    if total <= 0.0 {
+        return;
+    }
+    for (x, y, weight) in weights {
+        data[x + y * size.0] -= amount * weight / total;
+    }
+}
+
+/// Simulates a single water droplet starting at `pos`, eroding and
+/// depositing sediment into `data` as it flows downhill.
+fn simulate_droplet(data: &mut [f32], size: (usize, usize), mut pos: (f32, f32), params: &ErosionParams) {
+    let mut dir = (0.0, 0.0);
+    let mut speed = 1.0;
+    let mut water = 1.0;
+    let mut sediment = 0.0;
+
+    for _ in 0..params.max_lifetime {
+        let (height, gradient) = height_and_gradient(data, size, pos);
+
+        dir.0 = dir.0 * params.inertia - gradient.0 * (1.0 - params.inertia);
+        dir.1 = dir.1 * params.inertia - gradient.1 * (1.0 - params.inertia);
+        let len = (dir.0 * dir.0 + dir.1 * dir.1).sqrt().max(f32::EPSILON);
+        dir.0 /= len;
+        dir.1 /= len;
+
+        let new_pos = (pos.0 + dir.0, pos.1 + dir.1);
+        if new_pos.0 < 0.0 || new_pos.1 < 0.0 || new_pos.0 >= (size.0 - 1) as f32 || new_pos.1 >= (size.1 - 1) as f32
+        {
+            break;
+        }
+
+        let (new_height, _) = height_and_gradient(data, size, new_pos);
+        let height_diff = new_height - height;
+
+        let capacity = (-height_diff).max(params.min_slope) * speed * water * params.capacity_factor;
+
+        if height_diff > 0.0 || sediment > capacity {
+            let deposit_amount = if height_diff > 0.0 {
+                sediment.min(height_diff)
+            } else {
+                (sediment - capacity) * params.deposit_speed
+            };
+            sediment -= deposit_amount;
+            deposit(data, size, pos, deposit_amount);
+        } else {
+            let erode_amount = ((capacity - sediment) * params.erode_speed).min(-height_diff);
+            erode(data, size, pos, erode_amount, params.radius);
+            sediment += erode_amount;
+        }
+
+        speed = (speed * speed + height_diff * params.gravity).max(0.0).sqrt();
+        water *= 1.0 - params.evaporation;
+        pos = new_pos;
+
+        if water < 0.01 {
+            break;
+        }
+    }
+}
+
+/// A Whittaker-style biome classification, chosen from sea level, height,
+/// temperature and moisture.
+#[derive(Clone, Copy)]
+enum Biome {
+    Ocean,
+    Tundra,
+    Taiga,
+    Grassland,
+    Desert,
+    TemperateForest,
+    Rainforest,
+}
+
+impl Biome {
+    fn classify(height: f32, temperature: f32, moisture: f32, sea_level: f32) -> Self {
+        if height < sea_level {
+            return Biome::Ocean;
+        }
+        if temperature < 0.2 {
+            return Biome::Tundra;
+        }
+        if temperature < 0.45 {
+            return if moisture > 0.5 { Biome::Taiga } else { Biome::Grassland };
+        }
+        if moisture < 0.25 {
+            return Biome::Desert;
+        }
+        if moisture < 0.6 {
+            return Biome::Grassland;
+        }
+        if temperature < 0.75 {
+            Biome::TemperateForest
+        } else {
+            Biome::Rainforest
+        }
+    }
+
+    fn color(self) -> Rgb<u8> {
+        match self {
+            Biome::Ocean => Rgb([28, 82, 145]),
+            Biome::Tundra => Rgb([196, 206, 194]),
+            Biome::Taiga => Rgb([94, 130, 95]),
+            Biome::Grassland => Rgb([149, 186, 91]),
+            Biome::Desert => Rgb([222, 201, 137]),
+            Biome::TemperateForest => Rgb([66, 115, 61]),
+            Biome::Rainforest => Rgb([35, 90, 49]),
+        }
+    }
+}
+
+/// A sorted list of `(threshold, color)` stops used to tint a normalized
+/// height in `[0, 1]` instead of rendering it as flat gray.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ColorRamp {
+    stops: Vec<(f32, Rgb<u8>)>,
+}
+
+#[wasm_bindgen]
+impl ColorRamp {
+    /// Starts from the grayscale preset so the ramp always has at least one
+    /// stop to interpolate against; call [`ColorRamp::add_stop`] to customize it.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        ColorRamp::grayscale()
+    }
+
+    /// Adds a stop at `threshold` with the given color, keeping stops sorted.
+    /// A NaN `threshold` is ignored, since it has no defined sort position.
+    pub fn add_stop(&mut self, threshold: f32, r: u8, g: u8, b: u8) {
+        if threshold.is_nan() {
+            return;
+        }
+        self.stops.push((threshold, Rgb([r, g, b])));
+        self.stops
+            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    /// The original flat black-to-white ramp.
+    pub fn grayscale() -> ColorRamp {
+        ColorRamp {
+            stops: vec![(0.0, Rgb([0, 0, 0])), (1.0, Rgb([255, 255, 255]))],
+        }
+    }
+
+    /// Green lowlands rising through brown foothills to snow-capped peaks.
+    pub fn hypsometric() -> ColorRamp {
+        ColorRamp {
+            stops: vec![
+                (0.0, Rgb([60, 105, 56])),
+                (0.5, Rgb([181, 170, 94])),
+                (0.8, Rgb([120, 90, 60])),
+                (1.0, Rgb([255, 255, 255])),
+            ],
+        }
+    }
+
+    /// A ramp split at `sea_level`: a gradient from deep to shallow water
+    /// below it, and from coastline to inland green above it, with a hard
+    /// edge right at the coastline itself.
+    pub fn ocean_land(sea_level: f32) -> ColorRamp {
+        ColorRamp {
+            stops: vec![
+                (0.0, Rgb([16, 58, 110])),
+                (sea_level, Rgb([28, 107, 160])),
+                (sea_level, Rgb([194, 178, 128])),
+                (1.0, Rgb([61, 125, 64])),
+            ],
+        }
+    }
+}
+
+impl Default for ColorRamp {
+    fn default() -> Self {
+        ColorRamp::grayscale()
+    }
+}
+
+impl ColorRamp {
+    fn sample(&self, t: f32) -> Rgb<u8> {
+        let t = t.clamp(0.0, 1.0);
+        let stops = &self.stops;
+        if stops.is_empty() {
+            return Rgb([0, 0, 0]);
+        }
+        if t <= stops[0].0 {
+            return stops[0].1;
+        }
+        for window in stops.windows(2) {
+            let (t0, c0) = window[0];
+            let (t1, c1) = window[1];
+            if t <= t1 {
+                let f = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+                let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f) as u8;
+                return Rgb([lerp(c0.0[0], c1.0[0]), lerp(c0.0[1], c1.0[1]), lerp(c0.0[2], c1.0[2])]);
+            }
+        }
+        stops[stops.len() - 1].1
     }
 }
 
@@ -24,7 +452,13 @@ pub struct Map {
     size: (usize, usize),
     height: (f32, f32),
     roughness: usize,
+    noise_kind: NoiseKind,
     data: Vec<f32>,
+    sea_level: f32,
+    moisture: Vec<f32>,
+    temperature: Vec<f32>,
+    ramp: ColorRamp,
+    erosion_params: ErosionParams,
 }
 #[wasm_bindgen]
 impl Map {
@@ -34,10 +468,32 @@ impl Map {
             size: (size_x, size_y),
             height: (min_z, max_z),
             roughness,
+            noise_kind: NoiseKind::Fbm,
             data: Vec::new(),
+            sea_level: 0.3,
+            moisture: Vec::new(),
+            temperature: Vec::new(),
+            ramp: ColorRamp::default(),
+            erosion_params: ErosionParams::default(),
         }
     }
 
+    pub fn set_noise_kind(&mut self, noise_kind: NoiseKind) {
+        self.noise_kind = noise_kind;
+    }
+
+    pub fn set_sea_level(&mut self, sea_level: f32) {
+        self.sea_level = sea_level;
+    }
+
+    pub fn set_color_ramp(&mut self, ramp: ColorRamp) {
+        self.ramp = ramp;
+    }
+
+    pub fn set_erosion_params(&mut self, erosion_params: ErosionParams) {
+        self.erosion_params = erosion_params;
+    }
+
     pub fn generate_seeded(&mut self, seed: Option<u64>) {
         if let Some(seed) = seed {
             self.generate(&mut StdRng::seed_from_u64(seed));
@@ -46,6 +502,36 @@ impl Map {
         }
     }
 
+    /// Like [`Map::generate_seeded`], but samples noise on a unit sphere so
+    /// the result wraps seamlessly around a planet.
+    pub fn generate_spherical_seeded(&mut self, seed: Option<u64>) {
+        if let Some(seed) = seed {
+            self.generate_spherical(&mut StdRng::seed_from_u64(seed));
+        } else {
+            self.generate_spherical(&mut thread_rng());
+        }
+    }
+
+    /// Generates the companion moisture and temperature layers used by
+    /// [`Map::to_biome_image`].
+    pub fn generate_climate(&mut self, seed: Option<u64>) {
+        if let Some(seed) = seed {
+            self.generate_climate_inner(&mut StdRng::seed_from_u64(seed));
+        } else {
+            self.generate_climate_inner(&mut thread_rng());
+        }
+    }
+
+    /// Runs a droplet-based hydraulic erosion pass over the heightfield,
+    /// carving river valleys and depositing sediment fans.
+    pub fn erode(&mut self, droplets: usize, seed: Option<u64>) {
+        if let Some(seed) = seed {
+            self.erode_inner(droplets, &mut StdRng::seed_from_u64(seed));
+        } else {
+            self.erode_inner(droplets, &mut thread_rng());
+        }
+    }
+
     pub fn to_html_table(&self, table: HtmlTableElement) {
         let document = web_sys::window().unwrap().document().unwrap();
         table.set_inner_html("");
@@ -84,6 +570,38 @@ impl Map {
             .expect("could not encode image");
         format!("data:image/png;base64,{}", base64::encode(data))
     }
+
+    pub fn to_biome_data_uri(&self) -> String {
+        let mut data = Vec::new();
+        let image = self.to_biome_image();
+        image
+            .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+            .expect("could not encode image");
+        format!("data:image/png;base64,{}", base64::encode(data))
+    }
+
+    /// Like [`Map::to_data_uri`], but encodes the heightfield as a
+    /// single-channel 16-bit grayscale PNG instead of quantizing it to 8
+    /// bits of RGB, so the full dynamic range survives round-tripping.
+    pub fn to_heightmap_uri(&self) -> String {
+        let mut data = Vec::new();
+        let image = self.to_heightmap_image();
+        image
+            .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+            .expect("could not encode image");
+        format!("data:image/png;base64,{}", base64::encode(data))
+    }
+
+    /// Emits the heightfield as a 16-bit binary PGM (`P5`) buffer, the plain
+    /// interchange format most external heightmap importers read.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut data = format!("P5\n{} {}\n65535\n", self.size.0, self.size.1).into_bytes();
+        self.iter().for_each(|(_, h)| {
+            let v = (h.clamp(0.0, 1.0) * 65535.0) as u16;
+            data.extend_from_slice(&v.to_be_bytes());
+        });
+        data
+    }
 }
 impl Map {
     pub fn generate(&mut self, rng: &mut impl RngCore) {
@@ -94,19 +612,114 @@ impl Map {
         let mut freq = 1.0 / avg;
         let mut scale = 1.0;
 
+        let transform = |n: f32| self.noise_kind.apply(n);
+
+        let mut noise = FastNoise::seeded(rng.next_u64());
+        noise.set_frequency(freq);
+        generate_layer(&mut noise, self.size, 0.5 * scale, 0.5 * scale, transform, &mut data);
+        for _ in 0..self.roughness {
+            freq *= 4.0;
+            scale /= 6.0;
+            noise.set_seed(rng.next_u64());
+            noise.set_frequency(freq);
+            generate_layer(&mut noise, self.size, scale, 0.0, transform, &mut data);
+        }
+
+        if self.noise_kind != NoiseKind::Fbm {
+            normalize(&mut data);
+        }
+
+        self.data = data;
+    }
+
+    /// Spherical counterpart to [`Map::generate`]; maps each grid cell to a
+    /// point on a unit sphere before sampling noise, eliminating the seam
+    /// that the 2D path leaves when a map is wrapped or tiled.
+    pub fn generate_spherical(&mut self, rng: &mut impl RngCore) {
+        let mut data = Vec::new();
+        data.resize(self.size.0 * self.size.1, 0.0);
+
+        let avg = (self.size.0 as f32 + self.size.1 as f32) / 2.0;
+        let mut freq = 1.0 / avg;
+        let mut scale = 1.0;
+
+        let transform = |n: f32| self.noise_kind.apply(n);
+
         let mut noise = FastNoise::seeded(rng.next_u64());
         noise.set_frequency(freq);
-        generate_layer(&mut noise, self.size, 0.5 * scale, 0.5 * scale, &mut data);
+        generate_layer_spherical(&mut noise, self.size, 0.5 * scale, 0.5 * scale, transform, &mut data);
         for _ in 0..self.roughness {
             freq *= 4.0;
             scale /= 6.0;
             noise.set_seed(rng.next_u64());
             noise.set_frequency(freq);
-            generate_layer(&mut noise, self.size, scale, 0.0, &mut data);
+            generate_layer_spherical(&mut noise, self.size, scale, 0.0, transform, &mut data);
         }
+
+        if self.noise_kind != NoiseKind::Fbm {
+            normalize(&mut data);
+        }
+
         self.data = data;
     }
 
+    fn generate_climate_inner(&mut self, rng: &mut impl RngCore) {
+        let avg = (self.size.0 as f32 + self.size.1 as f32) / 2.0;
+
+        let mut moisture = Vec::new();
+        moisture.resize(self.size.0 * self.size.1, 0.0);
+        let mut noise = FastNoise::seeded(rng.next_u64());
+        noise.set_frequency(0.5 / avg);
+        generate_layer(&mut noise, self.size, 0.5, 0.5, |n| n, &mut moisture);
+
+        // How much a cell's temperature drops per unit of normalized altitude.
+        const LAPSE_RATE: f32 = 0.6;
+
+        let mut temperature = Vec::new();
+        temperature.resize(self.size.0 * self.size.1, 0.0);
+        for y in 0..self.size.1 {
+            let lat = y as f32 / (self.size.1.max(2) - 1) as f32;
+            let base = 1.0 - (lat - 0.5).abs() * 2.0;
+            for x in 0..self.size.0 {
+                let i = x + y * self.size.0;
+                // `generate()` may not have run yet; treat missing height as altitude 0.
+                let altitude = self.data.get(i).copied().unwrap_or(0.0).clamp(0.0, 1.0);
+                temperature[i] = (base - altitude * LAPSE_RATE).clamp(0.0, 1.0);
+            }
+        }
+
+        self.moisture = moisture;
+        self.temperature = temperature;
+    }
+
+    /// Renders the biome classification as an image. Cells are classified
+    /// with temperature and moisture of `0.0` until [`Map::generate_climate`]
+    /// has been called, rather than panicking on the as-yet-ungenerated data.
+    pub fn to_biome_image(&self) -> RgbImage {
+        let mut image = RgbImage::new(self.size.0 as _, self.size.1 as _);
+        self.iter().for_each(|((x, y), h)| {
+            let i = x + y * self.size.0;
+            let temperature = self.temperature.get(i).copied().unwrap_or(0.0);
+            let moisture = self.moisture.get(i).copied().unwrap_or(0.0);
+            let biome = Biome::classify(h.clamp(0.0, 1.0), temperature, moisture, self.sea_level);
+            image.put_pixel(x as _, y as _, biome.color());
+        });
+        image
+    }
+
+    fn erode_inner(&mut self, droplets: usize, rng: &mut impl RngCore) {
+        // `generate`/`generate_spherical` may not have run yet; there is no
+        // heightfield to erode.
+        if self.data.len() != self.size.0 * self.size.1 {
+            return;
+        }
+        for _ in 0..droplets {
+            let x = rng.next_u32() as f32 / u32::MAX as f32 * (self.size.0 - 1) as f32;
+            let y = rng.next_u32() as f32 / u32::MAX as f32 * (self.size.1 - 1) as f32;
+            simulate_droplet(&mut self.data, self.size, (x, y), &self.erosion_params);
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), f32)> + '_ {
         let width = self.size.0;
         self.data
@@ -119,8 +732,16 @@ impl Map {
     pub fn to_image(&self) -> RgbImage {
         let mut image = RgbImage::new(self.size.0 as _, self.size.1 as _);
         self.iter().for_each(|((x, y), h)| {
-            let c = (h.clamp(0.0, 1.0) * 255.0) as u8;
-            image.put_pixel(x as _, y as _, Rgb([c, c, c]));
+            image.put_pixel(x as _, y as _, self.ramp.sample(h));
+        });
+        image
+    }
+
+    pub fn to_heightmap_image(&self) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+        let mut image = ImageBuffer::new(self.size.0 as _, self.size.1 as _);
+        self.iter().for_each(|((x, y), h)| {
+            let v = (h.clamp(0.0, 1.0) * 65535.0) as u16;
+            image.put_pixel(x as _, y as _, Luma([v]));
         });
         image
     }